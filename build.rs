@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// A single row of `instructions.in`.
+struct InstructionDef {
+    mnemonic: String,
+    id: u8,
+    args: u8,
+    doc: String,
+}
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let in_path = Path::new(&manifest_dir).join("instructions.in");
+    println!("cargo:rerun-if-changed={}", in_path.display());
+
+    let source = fs::read_to_string(&in_path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", in_path.display(), e));
+
+    let defs = parse(&source);
+    check_unique(&defs);
+
+    let generated = generate(&defs);
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let out_path = Path::new(&out_dir).join("instructions.rs");
+    fs::write(&out_path, generated)
+        .unwrap_or_else(|e| panic!("failed to write {}: {}", out_path.display(), e));
+}
+
+fn parse(source: &str) -> Vec<InstructionDef> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            // mnemonic, id, args, "doc"
+            let (head, doc) = line
+                .split_once('"')
+                .unwrap_or_else(|| panic!("missing doc string in instruction line: {}", line));
+            let doc = doc.trim_end_matches('"').trim_end_matches(',').trim();
+
+            let mut fields = head.split(',').map(str::trim);
+            let mnemonic = fields
+                .next()
+                .unwrap_or_else(|| panic!("missing mnemonic in instruction line: {}", line))
+                .to_string();
+            let id: u8 = fields
+                .next()
+                .unwrap_or_else(|| panic!("missing id in instruction line: {}", line))
+                .parse()
+                .unwrap_or_else(|e| panic!("invalid id in instruction line {}: {}", line, e));
+            let args: u8 = fields
+                .next()
+                .unwrap_or_else(|| panic!("missing arg count in instruction line: {}", line))
+                .parse()
+                .unwrap_or_else(|e| panic!("invalid arg count in instruction line {}: {}", line, e));
+
+            assert!(id <= 0b111111, "id out of range (0-63) in line: {}", line);
+            assert!(args <= 3, "arg count out of range (0-3) in line: {}", line);
+
+            InstructionDef {
+                mnemonic,
+                id,
+                args,
+                doc: doc.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Fails the build if two instructions pack to the same opcode byte.
+fn check_unique(defs: &[InstructionDef]) {
+    let mut seen: HashMap<u8, &str> = HashMap::new();
+    for def in defs {
+        let opcode = (def.id << 2) | def.args;
+        if let Some(existing) = seen.insert(opcode, &def.mnemonic) {
+            panic!(
+                "instructions.in: `{}` and `{}` both pack to opcode byte 0b{:08b}",
+                existing, def.mnemonic, opcode
+            );
+        }
+    }
+}
+
+fn generate(defs: &[InstructionDef]) -> String {
+    let mut out = String::new();
+
+    out.push_str("// Generated by build.rs from instructions.in. Do not edit directly.\n\n");
+
+    out.push_str(
+        "#[derive(Debug, Copy, Clone, Eq, PartialEq, FromPrimitive, ToPrimitive, IntoEnumIterator)]\n",
+    );
+    out.push_str("#[repr(u8)]\n");
+    out.push_str("pub enum Instruction {\n");
+    for def in defs {
+        let opcode = (def.id << 2) | def.args;
+        let _ = writeln!(out, "    /// {}", def.doc);
+        let _ = writeln!(out, "    {} = {},", def.mnemonic, opcode);
+    }
+    out.push_str("}\n\n");
+
+    out.push_str("impl Instruction {\n");
+    out.push_str("    /// Bitmask to get the arguments from the instruction\n");
+    out.push_str("    const ARG_BIT_MASK: u8 = 0b000000_11;\n\n");
+    out.push_str("    /// Returns the mnemonic this instruction was declared with in `instructions.in`.\n");
+    out.push_str("    pub fn mnemonic(self) -> &'static str {\n");
+    out.push_str("        match self {\n");
+    for def in defs {
+        let _ = writeln!(out, "            Self::{} => \"{}\",", def.mnemonic, def.mnemonic);
+    }
+    out.push_str("        }\n");
+    out.push_str("    }\n");
+    out.push_str("}\n\n");
+
+    out.push_str("impl crate::processor::Instruction<u8> for Instruction {\n");
+    out.push_str("    // `Instruction` implements copy so the value is not moved with this invocation\n");
+    out.push_str("    fn get_args(self) -> usize {\n");
+    out.push_str("        // Get the instruction byte\n");
+    out.push_str("        let inst = self.try_into().unwrap_or(0u8);\n\n");
+    out.push_str("        // Get the last two bits of the instruction.\n");
+    out.push_str("        // These two bits represent the 0-3 byte argument requirement\n");
+    out.push_str("        (inst & Self::ARG_BIT_MASK).into()\n");
+    out.push_str("    }\n");
+    out.push_str("}\n\n");
+
+    out.push_str("// Converts an instruction into a byte\n");
+    out.push_str("impl TryFrom<Instruction> for u8 {\n");
+    out.push_str("    type Error = ();\n\n");
+    out.push_str("    fn try_from(value: Instruction) -> Result<Self, Self::Error> {\n");
+    out.push_str("        value.to_u8().map_or(Err(()), |val| Ok(val))\n");
+    out.push_str("    }\n");
+    out.push_str("}\n\n");
+
+    out.push_str("// Converts a byte to an instruction\n");
+    out.push_str("impl TryFrom<u8> for Instruction {\n");
+    out.push_str("    type Error = ();\n\n");
+    out.push_str("    fn try_from(value: u8) -> Result<Self, Self::Error> {\n");
+    out.push_str("        Self::from_u8(value).map_or(Err(()), |val| Ok(val))\n");
+    out.push_str("    }\n");
+    out.push_str("}\n");
+
+    out
+}
@@ -0,0 +1,378 @@
+//! A small textual assembly language for creature DNA, so programs can be authored and tested
+//! without writing raw instruction bytes by hand.
+//!
+//! Each line is an optional `label:` declaration followed by a mnemonic (matching
+//! [`Instruction::mnemonic`]) and its operand byte literals, separated by whitespace and/or
+//! commas. `;` starts a line comment. `Goto`, `GotoCondAGtB`, and `GotoCondEq` take a label name
+//! instead of a literal byte; it is resolved to the byte offset of the labelled instruction in
+//! the assembled stream. The result is a plain `Vec<u8>`, ready to hand to
+//! [`crate::creature::Creature::new_with`].
+//!
+//! ```text
+//! loop:
+//!     UAdd 1
+//!     Goto loop
+//! ```
+
+use crate::creature::Instruction;
+use crate::processor::Instruction as _;
+use crate::processor::InstructionCall;
+use enum_iterator::IntoEnumIterator;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fmt;
+
+/// A single problem found while assembling a program, tagged with the 1-based source line it came
+/// from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssembleError {
+    pub line: usize,
+    pub kind: AssembleErrorKind,
+}
+
+/// What went wrong on an [`AssembleError`]'s line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssembleErrorKind {
+    /// No instruction has this mnemonic.
+    UnknownMnemonic(String),
+    /// The mnemonic was given a different number of operands than its instruction expects.
+    WrongArgCount {
+        mnemonic: String,
+        expected: usize,
+        found: usize,
+    },
+    /// An operand (or resolved label offset) doesn't fit in a byte.
+    OperandOutOfRange(String),
+    /// A `Goto`-family operand named a label that was never declared.
+    UndefinedLabel(String),
+    /// The same label was declared more than once.
+    DuplicateLabel(String),
+}
+
+impl fmt::Display for AssembleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl fmt::Display for AssembleErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownMnemonic(mnemonic) => write!(f, "unknown mnemonic `{}`", mnemonic),
+            Self::WrongArgCount {
+                mnemonic,
+                expected,
+                found,
+            } => write!(
+                f,
+                "`{}` takes {} operand(s), found {}",
+                mnemonic, expected, found
+            ),
+            Self::OperandOutOfRange(token) => {
+                write!(f, "operand `{}` does not fit in a byte", token)
+            }
+            Self::UndefinedLabel(label) => write!(f, "undefined label `{}`", label),
+            Self::DuplicateLabel(label) => write!(f, "duplicate label `{}`", label),
+        }
+    }
+}
+
+impl std::error::Error for AssembleError {}
+
+/// One non-blank line of source, with its label and/or mnemonic/operand tokens split out but not
+/// yet resolved.
+struct ParsedLine<'a> {
+    line: usize,
+    label: Option<&'a str>,
+    mnemonic: Option<&'a str>,
+    operands: Vec<&'a str>,
+}
+
+fn is_label_name(token: &str) -> bool {
+    !token.is_empty() && token.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn parse_line(line: usize, raw: &str) -> Option<ParsedLine<'_>> {
+    let without_comment = raw.split(';').next().unwrap_or("");
+    let trimmed = without_comment.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    let (label, rest) = match trimmed.split_once(':') {
+        Some((name, rest)) if is_label_name(name.trim()) => (Some(name.trim()), rest.trim()),
+        _ => (None, trimmed),
+    };
+
+    let mut tokens = rest
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty());
+    let mnemonic = tokens.next();
+    let operands = tokens.collect();
+
+    Some(ParsedLine {
+        line,
+        label,
+        mnemonic,
+        operands,
+    })
+}
+
+fn lookup_mnemonic(mnemonic: &str) -> Option<Instruction> {
+    Instruction::into_enum_iter().find(|instruction| instruction.mnemonic() == mnemonic)
+}
+
+/// Parses a decimal (`-5`, `200`) or `0x`-prefixed hex (`0xFF`) byte literal, accepting the
+/// two's-complement encoding of negative values so signed operands (e.g. for `IAdd`) read
+/// naturally.
+fn parse_operand_literal(token: &str) -> Option<u8> {
+    if let Some(hex) = token.strip_prefix("0x").or_else(|| token.strip_prefix("0X")) {
+        return u8::from_str_radix(hex, 16).ok();
+    }
+
+    match token.parse::<i16>() {
+        Ok(value @ 0..=255) => Some(value as u8),
+        Ok(value @ -128..=-1) => Some(value as i8 as u8),
+        _ => None,
+    }
+}
+
+/// An instruction that has been resolved to a concrete [`Instruction`] and assigned its final byte
+/// offset, but whose operand tokens haven't been resolved to bytes yet.
+struct Emit<'a> {
+    line: usize,
+    instruction: Instruction,
+    operands: Vec<&'a str>,
+    offset: usize,
+}
+
+/// Assembles a textual DNA program into the raw bytecode [`crate::creature::Creature::new_with`]
+/// expects.
+///
+/// Returns every problem found, rather than stopping at the first one, so a whole program can be
+/// corrected in one pass.
+pub fn assemble(source: &str) -> Result<Vec<u8>, Vec<AssembleError>> {
+    let mut errors = Vec::new();
+
+    let parsed: Vec<ParsedLine> = source
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, raw)| parse_line(idx + 1, raw))
+        .collect();
+
+    // Pass 1: resolve mnemonics and assign byte offsets, recording label positions as we go.
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+    let mut emits: Vec<Emit> = Vec::new();
+    let mut offset = 0usize;
+
+    for pl in &parsed {
+        if let Some(label) = pl.label {
+            if labels.insert(label, offset).is_some() {
+                errors.push(AssembleError {
+                    line: pl.line,
+                    kind: AssembleErrorKind::DuplicateLabel(label.to_string()),
+                });
+            }
+        }
+
+        let mnemonic = match pl.mnemonic {
+            Some(mnemonic) => mnemonic,
+            None => continue, // label-only line
+        };
+
+        let instruction = match lookup_mnemonic(mnemonic) {
+            Some(instruction) => instruction,
+            None => {
+                errors.push(AssembleError {
+                    line: pl.line,
+                    kind: AssembleErrorKind::UnknownMnemonic(mnemonic.to_string()),
+                });
+                continue;
+            }
+        };
+
+        // Route the argument-count check through `InstructionCall` so it can't drift from the
+        // validation `new_*_arg` already enforces for every other caller.
+        let call = match pl.operands.as_slice() {
+            [] => InstructionCall::<u8, Instruction, &str>::new_0_arg(instruction),
+            [a] => InstructionCall::new_1_arg(instruction, *a),
+            [a, b] => InstructionCall::new_2_arg(instruction, *a, *b),
+            [a, b, c] => InstructionCall::new_3_arg(instruction, *a, *b, *c),
+            _ => Err(()),
+        };
+        if call.is_err() {
+            errors.push(AssembleError {
+                line: pl.line,
+                kind: AssembleErrorKind::WrongArgCount {
+                    mnemonic: mnemonic.to_string(),
+                    expected: instruction.get_args(),
+                    found: pl.operands.len(),
+                },
+            });
+            continue;
+        }
+
+        let arg_count = instruction.get_args();
+        emits.push(Emit {
+            line: pl.line,
+            instruction,
+            operands: pl.operands.clone(),
+            offset,
+        });
+        offset += 1 + arg_count;
+    }
+
+    // Pass 2: resolve operand bytes now that every label's offset is known.
+    let mut bytes = vec![0u8; offset];
+    for emit in &emits {
+        bytes[emit.offset] = emit.instruction.try_into().unwrap_or(0);
+
+        let is_goto = matches!(
+            emit.instruction,
+            Instruction::Goto | Instruction::GotoCondAGtB | Instruction::GotoCondEq
+        );
+
+        for (i, token) in emit.operands.iter().enumerate() {
+            let byte = if is_goto {
+                match labels.get(token) {
+                    Some(&target) if target <= u8::MAX as usize => Some(target as u8),
+                    Some(_) => {
+                        errors.push(AssembleError {
+                            line: emit.line,
+                            kind: AssembleErrorKind::OperandOutOfRange((*token).to_string()),
+                        });
+                        None
+                    }
+                    None => {
+                        errors.push(AssembleError {
+                            line: emit.line,
+                            kind: AssembleErrorKind::UndefinedLabel((*token).to_string()),
+                        });
+                        None
+                    }
+                }
+            } else {
+                parse_operand_literal(token)
+            };
+
+            match byte {
+                Some(byte) => bytes[emit.offset + 1 + i] = byte,
+                None => {
+                    if !is_goto {
+                        errors.push(AssembleError {
+                            line: emit.line,
+                            kind: AssembleErrorKind::OperandOutOfRange((*token).to_string()),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(bytes)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assembles_a_simple_loop() {
+        let bytes = assemble("loop:\n    UAdd 1\n    Goto loop\n").unwrap();
+
+        // `UAdd 1` at offset 0 (2 bytes), `Goto 0` at offset 2 (2 bytes), looping back to `loop`.
+        assert_eq!(bytes.len(), 4);
+        assert_eq!(bytes[2], Instruction::Goto.try_into().unwrap());
+        assert_eq!(bytes[3], 0);
+    }
+
+    #[test]
+    fn unknown_mnemonic_is_reported() {
+        let errors = assemble("Frobnicate\n").unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![AssembleError {
+                line: 1,
+                kind: AssembleErrorKind::UnknownMnemonic("Frobnicate".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn wrong_arg_count_is_reported() {
+        let errors = assemble("UAdd\n").unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![AssembleError {
+                line: 1,
+                kind: AssembleErrorKind::WrongArgCount {
+                    mnemonic: "UAdd".to_string(),
+                    expected: 1,
+                    found: 0,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn too_many_args_is_also_reported() {
+        let errors = assemble("UAdd 1 2\n").unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![AssembleError {
+                line: 1,
+                kind: AssembleErrorKind::WrongArgCount {
+                    mnemonic: "UAdd".to_string(),
+                    expected: 1,
+                    found: 2,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn undefined_label_is_reported() {
+        let errors = assemble("Goto nowhere\n").unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![AssembleError {
+                line: 1,
+                kind: AssembleErrorKind::UndefinedLabel("nowhere".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn duplicate_label_is_reported() {
+        let errors = assemble("a: None\na: None\n").unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![AssembleError {
+                line: 2,
+                kind: AssembleErrorKind::DuplicateLabel("a".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn out_of_range_operand_is_reported() {
+        let errors = assemble("UAdd 999\n").unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![AssembleError {
+                line: 1,
+                kind: AssembleErrorKind::OperandOutOfRange("999".to_string()),
+            }]
+        );
+    }
+}
@@ -1,9 +1,13 @@
-use crate::processor::ProcessorMemory;
-use enum_iterator::IntoEnumIterator;
-use num_traits::{FromPrimitive, ToPrimitive};
+//! Components, and the creature-facing instruction/memory plumbing, that depend on `specs` and so
+//! need the `std` feature (see how this module is gated in `lib.rs`). The instruction set itself
+//! lives in [`crate::instruction`] and compiles under `no_std`.
+
+use crate::processor::{ProcessorMemory, ProcessorProgram};
 use specs::prelude::*;
 use specs::Component;
-use std::convert::{TryFrom, TryInto};
+use std::convert::TryInto;
+
+pub use crate::instruction::Instruction;
 
 /// Creates a creature with the given instructions
 pub macro create_creature($($inst: ident),*) {
@@ -12,113 +16,6 @@ pub macro create_creature($($inst: ident),*) {
     ])
 }
 
-/// Represents an instruction that a creature tries to execute.
-///
-/// The most significant six bits represent the unique ID of the instruction and the least
-/// significant two bits represent the count of arguments for the instruction. Therefore,
-/// instructions may take 0, 1, 2, or 3 arguments (00, 01, 10, 11).
-///
-/// There may also be different variants of the same instruction, such as move with no argument
-/// being different than a move with one argument.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, FromPrimitive, ToPrimitive, IntoEnumIterator)]
-#[repr(u8)]
-pub enum Instruction {
-    //  Movement
-    /// Do nothing this tick
-    None = 0b000000_00,
-    /// Move forward 1 tile (will not move if unsuccessful).
-    Move = 0b000001_00,
-    /// Try to forward 2 tiles (will only move 1 if unsuccessful).
-    Jump = 0b000010_00,
-    /// Rotate clockwise (relative to looking down).
-    RotateCW = 0b000011_00,
-    /// Rotate counter-clockwise (relative to looking down).
-    RotateCCW = 0b000100_00,
-
-    //  Logic
-    /// Set mem A to 0.
-    ClearA = 0b000101_00,
-    /// Set mem B to 0.
-    ClearB = 0b000110_00,
-    /// Jump to the byte of DNA provided as an argument (loops back to start above length).
-    Goto = 0b000111_01,
-    /// If mem A is greater than the value in mem B, perform a jump to the argument.
-    GotoCondAGtB = 0b001000_01,
-    /// If mem B is equal to mem A, perform a jump to the argument.
-    GotoCondEq = 0b001001_01,
-    /// Switch mem A and mem B.
-    SwapAB = 0b001010_00,
-    /// Copy the value of mem A into mem TMP (but don't clear A)
-    CopyATmp = 0b001011_00,
-    /// Copy and clear the value of mem TMP into mem A.
-    LoadTmpA = 0b001100_00,
-    /// Copy and clear the value of mem TMP into mem B.
-    LoadTmpB = 0b001101_00,
-
-    // Creature
-    /// Load current health (0-max) into mem TMP.
-    StoreHealthTmp = 0b001110_00,
-    /// Load current fullness (0-max) into mem TMP.
-    StoreHungerTmp = 0b001111_00,
-    /// Load current fullness (0-max) into mem TMP.
-    StoreWasteTmp = 0b010110_00,
-    /// Load line of sight color hex (3 bytes) into mem TMP.
-    StoreLOSCTmp = 0b010000_00,
-
-    //  Math
-    /// Add the provided unsigned byte integer to signed mem A.
-    UAdd = 0b010001_01,
-    /// Add the provided signed byte integer to signed mem A.
-    IAdd = 0b010010_01,
-    /// Bitwise AND with A and B put into A.
-    BitAndB = 0b010011_00,
-    /// Bitwise AND with A and the argument put into A.
-    BitAnd = 0b010011_01,
-    /// Bitwise AND with A and B put into A.
-    BitOrB = 0b010100_00,
-    /// Bitwise AND with A and the argument put into A.
-    BitOr = 0b010100_01,
-    /// Bitwise X-OR with A and B put into A.
-    BitXorB = 0b010101_00,
-    /// Bitwise X-OR with A and the argument put into A.
-    BitXor = 0b010101_01,
-}
-
-impl Instruction {
-    /// Bitmask to get the arguments from the instruction
-    const ARG_BIT_MASK: u8 = 0b000000_11;
-}
-
-impl crate::processor::Instruction<u8> for Instruction {
-    // `Instruction` implements copy so the value is not moved with this invocation
-    fn get_args(self) -> usize {
-        // Get the instruction byte
-        let inst = self.try_into().unwrap_or(0u8);
-
-        // Get the last two bits of the instruction.
-        // These two bits represent the 0-3 byte argument requirement
-        (inst & Self::ARG_BIT_MASK).into()
-    }
-}
-
-// Converts a byte to an instruction
-impl TryFrom<Instruction> for u8 {
-    type Error = ();
-
-    fn try_from(value: Instruction) -> Result<Self, Self::Error> {
-        value.to_u8().map_or(Err(()), |val| Ok(val))
-    }
-}
-
-// Converts an instruction into a byte
-impl TryFrom<u8> for Instruction {
-    type Error = ();
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        Self::from_u8(value).map_or(Err(()), |val| Ok(val))
-    }
-}
-
 /// Represents the values in the creature's memory
 #[derive(Debug, Clone, PartialEq, Eq, Component)]
 #[storage(VecStorage)]
@@ -201,6 +98,27 @@ impl Creature {
     pub fn get_instructions_mut(&mut self) -> &mut Vec<u8> {
         &mut self.instructions
     }
+
+    /// The byte offset of the next opcode to be fetched.
+    pub(crate) fn current(&self) -> u64 {
+        self.current
+    }
+
+    /// Moves the fetch offset, wrapping is the caller's responsibility (see `Instruction::Goto`).
+    pub(crate) fn set_current(&mut self, current: u64) {
+        self.current = current;
+    }
+
+    /// The number of processor steps this creature has taken so far, counting steps that faulted
+    /// or found an empty instruction stream.
+    pub(crate) fn cycle(&self) -> u64 {
+        self.cycle
+    }
+
+    /// Bumps the step counter by one, wrapping instead of panicking on overflow.
+    pub(crate) fn increment_cycle(&mut self) {
+        self.cycle = self.cycle.wrapping_add(1);
+    }
 }
 
 impl Default for Creature {
@@ -208,3 +126,21 @@ impl Default for Creature {
         Self::new()
     }
 }
+
+impl ProcessorProgram for Creature {
+    fn get_instructions(&self) -> &[u8] {
+        &self.instructions
+    }
+
+    fn current(&self) -> u64 {
+        self.current()
+    }
+
+    fn set_current(&mut self, current: u64) {
+        self.set_current(current)
+    }
+
+    fn increment_cycle(&mut self) {
+        self.increment_cycle()
+    }
+}
@@ -0,0 +1,19 @@
+//! The creature instruction set: the `Instruction` enum, its `mnemonic()`, and the trait/
+//! conversion impls around it, generated by `build.rs` from the single source of truth in
+//! `instructions.in` (see that file to add, remove, or renumber instructions).
+//!
+//! This module only needs `core` plus the small set of derive-macro crates it uses, so it (along
+//! with [`crate::processor`]) compiles under `no_std`. Anything that ties an `Instruction` to an
+//! actual creature -- memory, position, direction, the ECS -- lives behind the `std` feature in
+//! [`crate::creature`] and [`crate::world`] instead.
+//!
+//! The most significant six bits of the generated discriminant are the unique ID of the
+//! instruction and the least significant two bits are the count of arguments for the instruction
+//! (0, 1, 2, or 3). There may also be different variants of the same instruction, such as move
+//! with no argument being different than a move with one argument.
+
+use core::convert::{TryFrom, TryInto};
+use enum_iterator::IntoEnumIterator;
+use num_traits::{FromPrimitive, ToPrimitive};
+
+include!(concat!(env!("OUT_DIR"), "/instructions.rs"));
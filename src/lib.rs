@@ -0,0 +1,31 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![feature(decl_macro)]
+
+// The instruction set and execution engine (`instruction`, `processor`) only need `core`/`alloc`
+// and compile under `no_std`, so they can be embedded on constrained or wasm targets where only
+// the creature VM is needed. Everything that ties them to an actual simulation -- the ECS
+// integration, the assembler/disassembler -- depends on `specs`/formatting and so is gated behind
+// the default `std` feature. `src/main.rs` is a thin `std`-only binary built on top of this lib.
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[macro_use]
+extern crate num_derive;
+
+pub mod instruction;
+pub mod processor;
+
+#[cfg(feature = "std")]
+pub mod assembler;
+#[cfg(feature = "std")]
+pub mod creature;
+#[cfg(all(feature = "std", feature = "disasm"))]
+pub mod disassembler;
+#[cfg(feature = "std")]
+pub mod world;
+
+#[cfg(not(feature = "std"))]
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
@@ -1,23 +1,20 @@
-#![feature(decl_macro)]
-
-#[macro_use]
-extern crate num_derive;
-
-pub mod creature;
-pub mod processor;
-pub mod world;
-
-use crate::creature::{Creature, CreatureMemory};
+//! A thin, `std`-only demo binary built on top of the `cces_rs` library (see `src/lib.rs`). The
+//! `instruction`/`processor` VM pieces are `no_std`-compatible and meant to be embedded by
+//! depending on this crate as a library instead -- this binary exists only to exercise them with
+//! the `specs`-backed ECS simulation.
+
+use cces_rs::creature::{self, Creature, CreatureMemory};
+use cces_rs::world::{self, CreatureDir, CreaturePos};
+use cces_rs::{instruction, processor};
 use enum_iterator::IntoEnumIterator;
 use processor::Instruction;
 use specs::{Builder, World, WorldExt};
 use std::convert::TryInto;
-use world::{CreatureDir, CreaturePos};
 
 fn main() {
     // Print out all of the instructions possible for debug purposes
     println!("Instructions: {{");
-    for instruction in creature::Instruction::into_enum_iter() {
+    for instruction in instruction::Instruction::into_enum_iter() {
         println!(
             "  [0b{0:06b}=0x{0:02X}] [{1}] {2:?}",
             (instruction.try_into().unwrap_or(0b00000000) >> 2),
@@ -1,7 +1,15 @@
+//! The generic instruction-call plumbing (`ProcessorMemory`, `Instruction`, `InstructionCall`) and
+//! the fetch/decode/dispatch engine (`Trap`, `Processor`) are both `no_std` compatible: `Processor`
+//! is generic over small traits (`ProcessorProgram`, `ProcessorPosition`, `ProcessorDirection`)
+//! instead of being tied to [`crate::creature::Creature`] or [`crate::world`]'s ECS components, so
+//! this module has no dependency on `specs` or `std`. The concrete `impl`s of those traits for the
+//! actual simulation live behind the `std` feature in [`crate::creature`] and [`crate::world`].
+
+use crate::instruction::Instruction as CreatureInstruction;
+use core::convert::{TryFrom, TryInto};
+use core::fmt::Debug;
+use core::marker::PhantomData;
 use num_traits::PrimInt;
-use std::convert::{TryFrom, TryInto};
-use std::fmt::Debug;
-use std::marker::PhantomData;
 
 /// A struct that will make structs with fields of this type constructable only within this module.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -126,3 +134,349 @@ impl<InstType: PrimInt, Inst: Instruction<InstType>, ArgType: Clone + PartialEq>
         Ok(Self::new_raw(instruction, None, None, None))
     }
 }
+
+/// A creature's raw bytecode stream plus the cursor/cycle-counter state the fetch/decode loop
+/// needs to step through it. Implemented by [`crate::creature::Creature`]; kept as a trait here so
+/// the engine itself has no dependency on `specs` or `std`.
+pub trait ProcessorProgram: Debug {
+    /// The raw instruction bytes.
+    fn get_instructions(&self) -> &[u8];
+
+    /// The byte offset of the next opcode to be fetched.
+    fn current(&self) -> u64;
+
+    /// Moves the fetch offset, wrapping is the caller's responsibility (see `Instruction::Goto`).
+    fn set_current(&mut self, current: u64);
+
+    /// Bumps the step counter by one, wrapping instead of panicking on overflow.
+    fn increment_cycle(&mut self);
+}
+
+/// The direction a creature is facing, and the rotations `RotateCW`/`RotateCCW` apply to it.
+/// Implemented by [`crate::world::CreatureDir`].
+pub trait ProcessorDirection: Copy + Debug {
+    /// Rotates clockwise (relative to looking down).
+    fn rotate_cw(self) -> Self;
+
+    /// Rotates counter-clockwise (relative to looking down).
+    fn rotate_ccw(self) -> Self;
+}
+
+/// A creature's position within the world, and the one movement primitive `Move`/`Jump` need.
+/// Implemented by [`crate::world::CreaturePos`].
+pub trait ProcessorPosition<Dir: ProcessorDirection>: Debug {
+    /// Attempts to move `tiles` squares in the given direction, failing (and leaving the position
+    /// untouched) if that would be out of bounds.
+    fn try_step(&mut self, dir: Dir, tiles: u64) -> bool;
+}
+
+/// Why a creature's bytecode execution was interrupted partway through a tick, instead of
+/// dispatching normally.
+///
+/// Every variant carries the byte `offset` execution faulted at so a disassembler (or a human
+/// staring at hex) can point straight at the offending instruction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Trap {
+    /// The opcode byte at `offset` doesn't map to a known [`crate::instruction::Instruction`].
+    InvalidOpcode { offset: usize, opcode: u8 },
+    /// The instruction at `offset` (`opcode`) needed more operand bytes than remained in the
+    /// stream.
+    TruncatedOperands { offset: usize, opcode: u8 },
+}
+
+/// Drives the fetch/decode/dispatch loop that turns a [`ProcessorProgram`]'s raw `instructions`
+/// byte stream into mutations of its memory, position, and facing direction.
+pub struct Processor;
+
+impl Processor {
+    /// Executes at most `budget` decoded instructions against a creature this tick, stopping
+    /// early the moment the instruction stream turns out to be empty (returning `Ok`) or a
+    /// [`Trap`] occurs (returning `Err` with that trap).
+    ///
+    /// Budget exhaustion simply ends the loop -- `ProcessorProgram::current` is left wherever the
+    /// last step put it, so the next tick's call resumes exactly where this one stopped. This is
+    /// what keeps a tight `Goto` loop in one creature's DNA from starving the rest of the
+    /// simulation: the `Processor` always yields control back to the caller after at most `budget`
+    /// steps.
+    ///
+    /// Returns the number of steps actually executed.
+    pub fn execute_tick<Prog, Mem, Dir, Pos>(
+        program: &mut Prog,
+        mem: &mut Mem,
+        pos: &mut Pos,
+        dir: &mut Dir,
+        budget: u32,
+    ) -> Result<u32, Trap>
+    where
+        Prog: ProcessorProgram,
+        Mem: ProcessorMemory<u64>,
+        Dir: ProcessorDirection,
+        Pos: ProcessorPosition<Dir>,
+    {
+        for step in 0..budget {
+            if !Self::step(program, mem, pos, dir)? {
+                return Ok(step);
+            }
+        }
+
+        Ok(budget)
+    }
+
+    /// Executes a single decoded instruction: fetches the opcode at `ProcessorProgram::current`,
+    /// decodes it into an [`crate::instruction::Instruction`] and its operand bytes, advances
+    /// `current` past it, and dispatches the resulting effect against `mem`/`pos`/`dir`.
+    ///
+    /// `ProcessorProgram::increment_cycle` is bumped exactly once per call, even when the step
+    /// turns out to be a no-op or faults, so it can serve as a hardware-timer-style step counter.
+    ///
+    /// Returns `Ok(false)` (rather than faulting) when the instruction stream is empty, since
+    /// there is nothing to fetch. Anything else that goes wrong while decoding -- an unknown
+    /// opcode byte, or operand bytes that run past the end of the stream -- is reported as a
+    /// [`Trap`] instead of being dispatched, with `current` left unmoved so the caller can decide
+    /// how to react (skip the tick, penalize the creature, etc.) before trying again.
+    fn step<Prog, Mem, Dir, Pos>(
+        program: &mut Prog,
+        mem: &mut Mem,
+        pos: &mut Pos,
+        dir: &mut Dir,
+    ) -> Result<bool, Trap>
+    where
+        Prog: ProcessorProgram,
+        Mem: ProcessorMemory<u64>,
+        Dir: ProcessorDirection,
+        Pos: ProcessorPosition<Dir>,
+    {
+        program.increment_cycle();
+
+        let len = program.get_instructions().len();
+        if len == 0 {
+            return Ok(false);
+        }
+
+        let offset = program.current() as usize % len;
+        let opcode = program.get_instructions()[offset];
+
+        let instruction = CreatureInstruction::try_from(opcode)
+            .map_err(|()| Trap::InvalidOpcode { offset, opcode })?;
+
+        let arg_count = instruction.get_args();
+        let mut args = [0u8; 3];
+        for (i, arg) in args.iter_mut().enumerate().take(arg_count) {
+            match program.get_instructions().get(offset + 1 + i) {
+                Some(&byte) => *arg = byte,
+                None => return Err(Trap::TruncatedOperands { offset, opcode }),
+            }
+        }
+
+        program.set_current(((offset + 1 + arg_count) % len) as u64);
+
+        Self::dispatch(instruction, args, len, program, mem, pos, dir);
+
+        Ok(true)
+    }
+
+    /// Applies the effect of a decoded instruction. `len` is the length of the creature's
+    /// instruction stream, used to wrap `Goto`-family jump targets.
+    #[allow(clippy::too_many_arguments)]
+    fn dispatch<Prog, Mem, Dir, Pos>(
+        instruction: CreatureInstruction,
+        args: [u8; 3],
+        len: usize,
+        program: &mut Prog,
+        mem: &mut Mem,
+        pos: &mut Pos,
+        dir: &mut Dir,
+    ) where
+        Prog: ProcessorProgram,
+        Mem: ProcessorMemory<u64>,
+        Dir: ProcessorDirection,
+        Pos: ProcessorPosition<Dir>,
+    {
+        use crate::instruction::Instruction::*;
+
+        match instruction {
+            None => {}
+            Move => {
+                pos.try_step(*dir, 1);
+            }
+            Jump => {
+                // Only fall back to a single tile if the full 2-tile jump was unsuccessful.
+                if !pos.try_step(*dir, 2) {
+                    pos.try_step(*dir, 1);
+                }
+            }
+            RotateCW => *dir = dir.rotate_cw(),
+            RotateCCW => *dir = dir.rotate_ccw(),
+            ClearA => mem.set_mem_a(0),
+            ClearB => mem.set_mem_b(0),
+            Goto => program.set_current(args[0] as u64 % len as u64),
+            GotoCondAGtB => {
+                if mem.get_mem_a() > mem.get_mem_b() {
+                    program.set_current(args[0] as u64 % len as u64);
+                }
+            }
+            GotoCondEq => {
+                if mem.get_mem_b() == mem.get_mem_a() {
+                    program.set_current(args[0] as u64 % len as u64);
+                }
+            }
+            SwapAB => {
+                let (a, b) = (mem.get_mem_a(), mem.get_mem_b());
+                mem.set_mem_a(b);
+                mem.set_mem_b(a);
+            }
+            CopyATmp => mem.set_mem_tmp(mem.get_mem_a()),
+            LoadTmpA => {
+                mem.set_mem_a(mem.get_mem_tmp());
+                mem.set_mem_tmp(0);
+            }
+            LoadTmpB => {
+                mem.set_mem_b(mem.get_mem_tmp());
+                mem.set_mem_tmp(0);
+            }
+            // Health/hunger/waste/line-of-sight telemetry isn't modeled on `Creature` yet, so
+            // these are no-ops for now rather than fabricating a value.
+            StoreHealthTmp | StoreHungerTmp | StoreWasteTmp | StoreLOSCTmp => {}
+            UAdd => mem.set_mem_a(mem.get_mem_a().wrapping_add(args[0] as u64)),
+            IAdd => mem.set_mem_a(mem.get_mem_a().wrapping_add((args[0] as i8 as i64) as u64)),
+            BitAndB => mem.set_mem_a(mem.get_mem_a() & mem.get_mem_b()),
+            BitAnd => mem.set_mem_a(mem.get_mem_a() & args[0] as u64),
+            BitOrB => mem.set_mem_a(mem.get_mem_a() | mem.get_mem_b()),
+            BitOr => mem.set_mem_a(mem.get_mem_a() | args[0] as u64),
+            BitXorB => mem.set_mem_a(mem.get_mem_a() ^ mem.get_mem_b()),
+            BitXor => mem.set_mem_a(mem.get_mem_a() ^ args[0] as u64),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::creature::{Creature, CreatureMemory};
+    use crate::world::{CreatureDir, CreaturePos};
+    use std::convert::TryInto;
+
+    fn opcode(instruction: CreatureInstruction) -> u8 {
+        instruction.try_into().unwrap()
+    }
+
+    fn harness(
+        instructions: Vec<u8>,
+    ) -> (Creature, CreatureMemory, CreaturePos, CreatureDir) {
+        (
+            Creature::new_with(instructions),
+            CreatureMemory::new(),
+            CreaturePos::default(),
+            CreatureDir::default(),
+        )
+    }
+
+    #[test]
+    fn empty_program_ends_the_tick_without_faulting() {
+        let (mut creature, mut mem, mut pos, mut dir) = harness(vec![]);
+
+        let steps = Processor::execute_tick(&mut creature, &mut mem, &mut pos, &mut dir, 5)
+            .expect("an empty program should not fault");
+
+        assert_eq!(steps, 0);
+        // `increment_cycle` still runs once before the empty check short-circuits the step.
+        assert_eq!(creature.cycle(), 1);
+    }
+
+    #[test]
+    fn truncated_operands_fault_instead_of_reading_out_of_bounds() {
+        // `Goto` takes 1 operand byte, but the stream ends right after its opcode.
+        let (mut creature, mut mem, mut pos, mut dir) =
+            harness(vec![opcode(CreatureInstruction::Goto)]);
+
+        let err = Processor::execute_tick(&mut creature, &mut mem, &mut pos, &mut dir, 1)
+            .expect_err("a missing operand byte should fault");
+
+        assert_eq!(
+            err,
+            Trap::TruncatedOperands {
+                offset: 0,
+                opcode: opcode(CreatureInstruction::Goto),
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_opcode_faults_with_its_offset() {
+        // id 63 / 3 args was never assigned in `instructions.in`, so this byte can't decode.
+        let (mut creature, mut mem, mut pos, mut dir) = harness(vec![0xFF]);
+
+        let err = Processor::execute_tick(&mut creature, &mut mem, &mut pos, &mut dir, 1)
+            .expect_err("an unassigned opcode byte should fault");
+
+        assert_eq!(
+            err,
+            Trap::InvalidOpcode {
+                offset: 0,
+                opcode: 0xFF,
+            }
+        );
+    }
+
+    #[test]
+    fn goto_target_wraps_at_the_instruction_length() {
+        // `Goto 7` on a 2-byte program should wrap to offset `7 % 2 == 1`.
+        let (mut creature, mut mem, mut pos, mut dir) =
+            harness(vec![opcode(CreatureInstruction::Goto), 7]);
+
+        Processor::execute_tick(&mut creature, &mut mem, &mut pos, &mut dir, 1)
+            .expect("dispatching Goto should not fault");
+
+        assert_eq!(creature.current(), 1);
+    }
+
+    #[test]
+    fn budget_exhaustion_stops_the_loop_and_preserves_state_for_the_next_tick() {
+        // A `Goto 0` on a 2-byte program loops back onto itself forever.
+        let (mut creature, mut mem, mut pos, mut dir) =
+            harness(vec![opcode(CreatureInstruction::Goto), 0]);
+
+        let steps = Processor::execute_tick(&mut creature, &mut mem, &mut pos, &mut dir, 3)
+            .expect("a tight Goto loop should not fault");
+        assert_eq!(steps, 3);
+        assert_eq!(creature.cycle(), 3);
+        assert_eq!(creature.current(), 0);
+
+        // The next tick resumes exactly where this one left off instead of restarting.
+        let steps = Processor::execute_tick(&mut creature, &mut mem, &mut pos, &mut dir, 2)
+            .expect("a tight Goto loop should not fault");
+        assert_eq!(steps, 2);
+        assert_eq!(creature.cycle(), 5);
+        assert_eq!(creature.current(), 0);
+    }
+
+    #[test]
+    fn uadd_wraps_into_mem_a() {
+        let (mut creature, mut mem, mut pos, mut dir) =
+            harness(vec![opcode(CreatureInstruction::UAdd), 200]);
+        mem.set_mem_a(u64::MAX - 50);
+
+        Processor::execute_tick(&mut creature, &mut mem, &mut pos, &mut dir, 1)
+            .expect("UAdd should not fault");
+
+        assert_eq!(mem.get_mem_a(), 149);
+    }
+
+    #[test]
+    fn rotate_cw_cycles_through_every_direction() {
+        let (mut creature, mut mem, mut pos, mut dir) =
+            harness(vec![opcode(CreatureInstruction::RotateCW)]);
+
+        for expected in [
+            CreatureDir::East,
+            CreatureDir::South,
+            CreatureDir::West,
+            CreatureDir::North,
+        ] {
+            creature.set_current(0);
+            Processor::execute_tick(&mut creature, &mut mem, &mut pos, &mut dir, 1)
+                .expect("RotateCW should not fault");
+            assert_eq!(dir, expected);
+        }
+    }
+}
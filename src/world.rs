@@ -1,4 +1,5 @@
 use crate::creature::{Creature, CreatureMemory};
+use crate::processor::{Processor, ProcessorDirection, ProcessorPosition};
 use specs::prelude::*;
 use specs::{Component, SystemData};
 
@@ -11,10 +12,30 @@ pub macro create_world($($component_type: ty),*) {{
     // Register components
     $( ::specs::WorldExt::register::<$component_type>(&mut world); )*
 
+    // Seed the resources every system expects to find. `insert` is an inherent method on
+    // `specs::World` itself, not part of the `WorldExt` trait, so it can't be called the same way
+    // as `new`/`register` above.
+    ::specs::World::insert(&mut world, crate::world::CycleBudget::default());
+
     // Return world
     world
 }}
 
+/// The maximum number of instructions a creature's [`Processor`](crate::processor::Processor) may
+/// execute in a single tick.
+///
+/// This trades fairness (every creature's tick returns to the ECS promptly) against throughput
+/// (how much a tight `Goto` loop can get done per tick); raise it to let creatures do more work
+/// per tick at the cost of ticks taking longer to simulate.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CycleBudget(pub u32);
+
+impl Default for CycleBudget {
+    fn default() -> Self {
+        Self(64)
+    }
+}
+
 /// Represents the position of a creature within the world.
 #[allow(dead_code)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Component, Default)]
@@ -24,6 +45,28 @@ pub struct CreaturePos {
     y: u64,
 }
 
+impl ProcessorPosition<CreatureDir> for CreaturePos {
+    /// Attempts to move `tiles` squares in the given direction, failing (and leaving the position
+    /// untouched) if that would underflow past the world origin.
+    fn try_step(&mut self, dir: CreatureDir, tiles: u64) -> bool {
+        let moved = match dir {
+            CreatureDir::North => self.y.checked_add(tiles).map(|y| (self.x, y)),
+            CreatureDir::South => self.y.checked_sub(tiles).map(|y| (self.x, y)),
+            CreatureDir::East => self.x.checked_add(tiles).map(|x| (x, self.y)),
+            CreatureDir::West => self.x.checked_sub(tiles).map(|x| (x, self.y)),
+        };
+
+        match moved {
+            Some((x, y)) => {
+                self.x = x;
+                self.y = y;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
 /// Represents a possible direction the creature might be facing.
 #[allow(dead_code)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Component)]
@@ -48,6 +91,28 @@ impl Default for CreatureDir {
     }
 }
 
+impl ProcessorDirection for CreatureDir {
+    /// Rotates clockwise (relative to looking down), i.e. North -> East -> South -> West -> North.
+    fn rotate_cw(self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::East => Self::South,
+            Self::South => Self::West,
+            Self::West => Self::North,
+        }
+    }
+
+    /// Rotates counter-clockwise (relative to looking down).
+    fn rotate_ccw(self) -> Self {
+        match self {
+            Self::North => Self::West,
+            Self::West => Self::South,
+            Self::South => Self::East,
+            Self::East => Self::North,
+        }
+    }
+}
+
 /// Represents a data structure that contains all the information needed to perform a tick on a
 /// creature.
 #[allow(dead_code)]
@@ -57,6 +122,7 @@ struct CreatureTickData<'a> {
     creature_dir: WriteStorage<'a, CreatureDir>,
     creature_mem: WriteStorage<'a, CreatureMemory>,
     creature: WriteStorage<'a, Creature>,
+    cycle_budget: Read<'a, CycleBudget>,
 }
 
 /// The system responsible for ticking each creature. This is essentially the brain tick stage of
@@ -67,14 +133,29 @@ impl<'a> System<'a> for CreatureTickSystem {
     type SystemData = CreatureTickData<'a>;
 
     fn run(&mut self, data: Self::SystemData) {
+        let CreatureTickData {
+            mut creature_pos,
+            mut creature_dir,
+            mut creature_mem,
+            mut creature,
+            cycle_budget,
+        } = data;
+
         for (pos, dir, mem, creature) in (
-            &data.creature_pos,
-            &data.creature_dir,
-            &data.creature_mem,
-            &data.creature,
+            &mut creature_pos,
+            &mut creature_dir,
+            &mut creature_mem,
+            &mut creature,
         )
             .join()
         {
+            // No health/energy system exists yet to meaningfully penalize a faulted creature, so
+            // for now a trap just skips the rest of this tick instead of being treated as a
+            // silent no-op.
+            if let Err(trap) = Processor::execute_tick(creature, mem, pos, dir, cycle_budget.0) {
+                eprintln!("creature faulted: {:?}", trap);
+            }
+
             println!("{:X?}\n{:#X?}\n{:#X?}\n{:#X?}", pos, dir, mem, creature);
         }
     }